@@ -0,0 +1,299 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::widget::Tree;
+use iced::advanced::{renderer, text, Clipboard, Shell, Widget};
+use iced::alignment;
+use iced::mouse;
+use iced::{Border, Color, Element, Event, Length, Pixels, Point, Rectangle, Size};
+
+use crate::events::EventsCollection;
+use crate::{iso_week_number, Message};
+
+const COLUMNS: usize = 7;
+pub(crate) const CELL_WIDTH: f32 = 90.0;
+const CELL_HEIGHT: f32 = 70.0;
+pub(crate) const ISO_WEEK_COLUMN_WIDTH: f32 = 36.0;
+/// How many event bars are painted in a day cell before the rest are
+/// collapsed into a "+k more" label. Shared with `day_cell` in `main.rs` so
+/// the Month grid and the Year/Week views can't drift apart.
+pub(crate) const MAX_EVENT_BARS: usize = 3;
+
+/// A single iced [`Widget`] that lays out and paints an entire month grid in
+/// one pass, instead of nesting 35-42 separate button/text widgets. This
+/// keeps cell sizing consistent and centralizes the today marker,
+/// out-of-month dimming, selected-day highlight, and event-bar painting that used
+/// to be scattered across per-cell button styles.
+pub struct CalendarGrid<'a> {
+    first_cell: NaiveDate,
+    week_count: usize,
+    month: u32,
+    today: NaiveDate,
+    selected: Option<NaiveDate>,
+    events: &'a EventsCollection,
+    show_iso_week: bool,
+}
+
+impl<'a> CalendarGrid<'a> {
+    /// `first_cell` is the leading day shown in the grid (may fall in the
+    /// previous month, already aligned to the configured week-start day);
+    /// `week_count` is how many rows of 7 days to paint. When `show_iso_week`
+    /// is set, an extra leftmost column shows each row's ISO week number.
+    pub fn new(
+        first_cell: NaiveDate,
+        week_count: usize,
+        month: u32,
+        today: NaiveDate,
+        selected: Option<NaiveDate>,
+        events: &'a EventsCollection,
+        show_iso_week: bool,
+    ) -> Self {
+        Self {
+            first_cell,
+            week_count,
+            month,
+            today,
+            selected,
+            events,
+            show_iso_week,
+        }
+    }
+
+    fn cell_date(&self, index: usize) -> NaiveDate {
+        self.first_cell + Duration::days(index as i64)
+    }
+
+    fn iso_week_column_offset(&self) -> f32 {
+        if self.show_iso_week {
+            ISO_WEEK_COLUMN_WIDTH
+        } else {
+            0.0
+        }
+    }
+
+    fn total_width(&self) -> f32 {
+        self.iso_week_column_offset() + CELL_WIDTH * COLUMNS as f32
+    }
+
+    fn cell_rect(&self, layout: &Rectangle, index: usize) -> Rectangle {
+        let column = (index % COLUMNS) as f32;
+        let row = (index / COLUMNS) as f32;
+        Rectangle {
+            x: layout.x + self.iso_week_column_offset() + column * CELL_WIDTH,
+            y: layout.y + row * CELL_HEIGHT,
+            width: CELL_WIDTH,
+            height: CELL_HEIGHT,
+        }
+    }
+}
+
+impl<'a, Renderer> Widget<Message, iced::Theme, Renderer> for CalendarGrid<'a>
+where
+    Renderer: text::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: Length::Fixed(self.total_width()),
+            height: Length::Fixed(CELL_HEIGHT * self.week_count as f32),
+        }
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(Size::new(
+            self.total_width(),
+            CELL_HEIGHT * self.week_count as f32,
+        ))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &iced::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        if self.show_iso_week {
+            for row in 0..self.week_count {
+                let week_number = iso_week_number(self.cell_date(row * COLUMNS));
+                let row_bounds = Rectangle {
+                    x: bounds.x,
+                    y: bounds.y + row as f32 * CELL_HEIGHT,
+                    width: ISO_WEEK_COLUMN_WIDTH,
+                    height: CELL_HEIGHT,
+                };
+                renderer.fill_text(
+                    text::Text {
+                        content: week_number.to_string(),
+                        bounds: Size::new(row_bounds.width - 4.0, 16.0),
+                        size: Pixels(12.0),
+                        line_height: text::LineHeight::default(),
+                        font: renderer.default_font(),
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Top,
+                        shaping: text::Shaping::Basic,
+                        wrapping: text::Wrapping::None,
+                    },
+                    Point::new(row_bounds.x + 2.0, row_bounds.y + CELL_HEIGHT / 2.0 - 8.0),
+                    Color::from_rgb(0.6, 0.6, 0.6),
+                    row_bounds,
+                );
+            }
+        }
+
+        for index in 0..self.week_count * COLUMNS {
+            let date = self.cell_date(index);
+            let cell = self.cell_rect(&bounds, index);
+
+            let in_month = date.month() == self.month;
+            let is_today = date == self.today;
+            let is_selected = self.selected == Some(date);
+
+            let background = if is_selected {
+                Color::from_rgb(0.30, 0.45, 0.80)
+            } else if in_month {
+                Color::from_rgb(0.18, 0.18, 0.20)
+            } else {
+                Color::from_rgb(0.12, 0.12, 0.13)
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: cell,
+                    border: Border {
+                        color: if is_today {
+                            Color::from_rgb(0.95, 0.75, 0.2)
+                        } else {
+                            Color::from_rgb(0.05, 0.05, 0.05)
+                        },
+                        width: if is_today { 2.0 } else { 1.0 },
+                        radius: 4.0.into(),
+                    },
+                    shadow: Default::default(),
+                },
+                background,
+            );
+
+            renderer.fill_text(
+                text::Text {
+                    content: date.day().to_string(),
+                    bounds: Size::new(cell.width - 8.0, 16.0),
+                    size: Pixels(16.0),
+                    line_height: text::LineHeight::default(),
+                    font: renderer.default_font(),
+                    horizontal_alignment: alignment::Horizontal::Left,
+                    vertical_alignment: alignment::Vertical::Top,
+                    shaping: text::Shaping::Basic,
+                    wrapping: text::Wrapping::None,
+                },
+                Point::new(cell.x + 4.0, cell.y + 4.0),
+                if in_month {
+                    Color::WHITE
+                } else {
+                    Color::from_rgb(0.5, 0.5, 0.5)
+                },
+                cell,
+            );
+
+            let day_events = self.events.for_date(&date);
+            for (bar_index, event) in day_events.iter().take(MAX_EVENT_BARS).enumerate() {
+                let bar_y = cell.y + 22.0 + bar_index as f32 * 14.0;
+                renderer.fill_text(
+                    text::Text {
+                        content: event.title.clone(),
+                        bounds: Size::new(cell.width - 8.0, 12.0),
+                        size: Pixels(10.0),
+                        line_height: text::LineHeight::default(),
+                        font: renderer.default_font(),
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Top,
+                        shaping: text::Shaping::Basic,
+                        wrapping: text::Wrapping::None,
+                    },
+                    Point::new(cell.x + 4.0, bar_y),
+                    Color::from_rgb(0.7, 0.85, 1.0),
+                    cell,
+                );
+            }
+
+            if day_events.len() > MAX_EVENT_BARS {
+                let more_y = cell.y + 22.0 + MAX_EVENT_BARS as f32 * 14.0;
+                renderer.fill_text(
+                    text::Text {
+                        content: format!("+{} more", day_events.len() - MAX_EVENT_BARS),
+                        bounds: Size::new(cell.width - 8.0, 12.0),
+                        size: Pixels(10.0),
+                        line_height: text::LineHeight::default(),
+                        font: renderer.default_font(),
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Top,
+                        shaping: text::Shaping::Basic,
+                        wrapping: text::Wrapping::None,
+                    },
+                    Point::new(cell.x + 4.0, more_y),
+                    Color::from_rgb(0.6, 0.6, 0.6),
+                    cell,
+                );
+            }
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> iced::event::Status {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(position) = cursor.position() {
+                let bounds = layout.bounds();
+                for index in 0..self.week_count * COLUMNS {
+                    let cell = self.cell_rect(&bounds, index);
+                    if cell.contains(position) {
+                        shell.publish(Message::DateSelected(self.cell_date(index)));
+                        return iced::event::Status::Captured;
+                    }
+                }
+            }
+        }
+
+        iced::event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a, Renderer> From<CalendarGrid<'a>> for Element<'a, Message, iced::Theme, Renderer>
+where
+    Renderer: text::Renderer + 'a,
+{
+    fn from(grid: CalendarGrid<'a>) -> Self {
+        Element::new(grid)
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use icalendar::{Calendar, CalendarComponent, Component, DatePerhapsTime};
+
+/// A single appointment parsed out of an `.ics` file.
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub title: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub all_day: bool,
+}
+
+/// Parsed calendar events, grouped by the day they occur on.
+///
+/// Recurring events (`RRULE`) are expanded eagerly at load time into one
+/// [`CalendarEvent`] per occurrence within the requested visible range, so
+/// callers can look events up by plain `NaiveDate` without knowing anything
+/// about recurrence.
+#[derive(Debug, Default, Clone)]
+pub struct EventsCollection {
+    by_date: HashMap<NaiveDate, Vec<CalendarEvent>>,
+}
+
+impl EventsCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads and merges events from every `.ics` file in `paths`, expanding
+    /// simple daily/weekly `RRULE`s into occurrences that fall within
+    /// `visible_range` (inclusive on both ends). Files that are missing or
+    /// fail to parse are skipped rather than failing the whole load, since a
+    /// single bad calendar shouldn't prevent the app from starting.
+    pub fn load_from_files<P: AsRef<Path>>(
+        paths: &[P],
+        visible_range: (NaiveDate, NaiveDate),
+    ) -> Self {
+        let mut collection = Self::new();
+        for path in paths {
+            if let Ok(contents) = std::fs::read_to_string(path) {
+                collection.load_from_str(&contents, visible_range);
+            }
+        }
+        collection
+    }
+
+    fn load_from_str(&mut self, contents: &str, visible_range: (NaiveDate, NaiveDate)) {
+        let Ok(calendar) = contents.parse::<Calendar>() else {
+            return;
+        };
+
+        for component in &calendar.components {
+            if let CalendarComponent::Event(event) = component {
+                self.add_event(event, visible_range);
+            }
+        }
+    }
+
+    fn add_event(&mut self, event: &icalendar::Event, visible_range: (NaiveDate, NaiveDate)) {
+        let Some(start) = event.get_start().and_then(date_perhaps_time_to_naive) else {
+            return;
+        };
+        let end = event
+            .get_end()
+            .and_then(date_perhaps_time_to_naive)
+            .unwrap_or(start);
+        let all_day = matches!(event.get_start(), Some(DatePerhapsTime::Date(_)));
+        let title = event.get_summary().unwrap_or("(untitled)").to_string();
+
+        for occurrence_start in expand_recurrence(event.property_value("RRULE"), start, visible_range)
+        {
+            let shift = occurrence_start - start;
+            self.by_date
+                .entry(occurrence_start.date())
+                .or_default()
+                .push(CalendarEvent {
+                    title: title.clone(),
+                    start: occurrence_start,
+                    end: end + shift,
+                    all_day,
+                });
+        }
+    }
+
+    /// Returns the events occurring on `date`, sorted by start time.
+    pub fn for_date(&self, date: &NaiveDate) -> Vec<&CalendarEvent> {
+        let mut events: Vec<&CalendarEvent> = self
+            .by_date
+            .get(date)
+            .map(|events| events.iter().collect())
+            .unwrap_or_default();
+        events.sort_by_key(|event| event.start);
+        events
+    }
+}
+
+/// A parsed `RRULE`, covering only the `FREQ`/`COUNT`/`UNTIL` parts needed
+/// for simple daily/weekly recurrences.
+struct RecurrenceRule {
+    step: Duration,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+}
+
+/// Parses an `RRULE` value like `FREQ=WEEKLY;COUNT=5` or
+/// `FREQ=DAILY;UNTIL=20260815T000000Z`. Returns `None` for anything other
+/// than `FREQ=DAILY`/`FREQ=WEEKLY`, since that covers the common appointment
+/// case.
+fn parse_rrule(rrule: &str) -> Option<RecurrenceRule> {
+    let mut step = None;
+    let mut count = None;
+    let mut until = None;
+
+    for part in rrule.split(';') {
+        let mut key_value = part.splitn(2, '=');
+        let key = key_value.next()?;
+        let value = key_value.next()?;
+
+        match key {
+            "FREQ" => {
+                step = match value {
+                    "DAILY" => Some(Duration::days(1)),
+                    "WEEKLY" => Some(Duration::weeks(1)),
+                    _ => return None,
+                };
+            }
+            "COUNT" => count = value.parse::<usize>().ok(),
+            "UNTIL" => until = parse_ical_datetime(value),
+            _ => {}
+        }
+    }
+
+    step.map(|step| RecurrenceRule { step, count, until })
+}
+
+fn parse_ical_datetime(value: &str) -> Option<NaiveDateTime> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Some(date_time);
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+}
+
+/// Expands a (possibly absent) `RRULE` into the start timestamps of every
+/// occurrence within `visible_range`, honoring `COUNT`/`UNTIL` so a rule like
+/// `FREQ=WEEKLY;COUNT=5` produces exactly 5 occurrences instead of one for
+/// every week in the visible range. An absent or unsupported `RRULE` yields
+/// just `first_start`, whether or not it falls within `visible_range`.
+fn expand_recurrence(
+    rrule: Option<&str>,
+    first_start: NaiveDateTime,
+    visible_range: (NaiveDate, NaiveDate),
+) -> Vec<NaiveDateTime> {
+    let (range_start, range_end) = visible_range;
+
+    let Some(rrule) = rrule else {
+        return vec![first_start];
+    };
+
+    let Some(rule) = parse_rrule(rrule) else {
+        return vec![first_start];
+    };
+
+    let mut occurrences = Vec::new();
+    let mut current = first_start;
+    let mut seen = 0usize;
+
+    loop {
+        if rule.count.is_some_and(|count| seen >= count) {
+            break;
+        }
+        if rule.until.is_some_and(|until| current > until) {
+            break;
+        }
+        if current.date() > range_end {
+            break;
+        }
+
+        if current.date() >= range_start {
+            occurrences.push(current);
+        }
+
+        seen += 1;
+        current += rule.step;
+    }
+
+    occurrences
+}
+
+fn date_perhaps_time_to_naive(value: DatePerhapsTime) -> Option<NaiveDateTime> {
+    match value {
+        DatePerhapsTime::DateTime(date_time) => date_time.try_into_utc().map(|dt| dt.naive_utc()),
+        DatePerhapsTime::Date(date) => date.and_hms_opt(0, 0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+
+    fn naive_datetime(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn count_bounds_weekly_recurrence_regardless_of_visible_range() {
+        let first_start = naive_datetime(2026, 1, 5);
+        let wide_range = (
+            NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+        );
+
+        let occurrences = expand_recurrence(Some("FREQ=WEEKLY;COUNT=5"), first_start, wide_range);
+
+        assert_eq!(occurrences.len(), 5);
+        assert_eq!(occurrences[0], first_start);
+        assert_eq!(occurrences[4], first_start + Duration::weeks(4));
+    }
+
+    #[test]
+    fn until_stops_daily_recurrence_on_the_right_day() {
+        let first_start = naive_datetime(2026, 3, 1);
+        let wide_range = (
+            NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+        );
+
+        let occurrences = expand_recurrence(
+            Some("FREQ=DAILY;UNTIL=20260303T000000Z"),
+            first_start,
+            wide_range,
+        );
+
+        // UNTIL is midnight on Mar 3, so the 09:00 occurrence that day is past it.
+        assert_eq!(occurrences.len(), 2);
+        assert_eq!(occurrences.last().unwrap().date().day(), 2);
+    }
+
+    #[test]
+    fn visible_range_still_filters_unbounded_recurrence() {
+        let first_start = naive_datetime(2026, 1, 1);
+        let narrow_range = (
+            NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 20).unwrap(),
+        );
+
+        let occurrences = expand_recurrence(Some("FREQ=DAILY"), first_start, narrow_range);
+
+        assert_eq!(occurrences.len(), 11);
+        assert_eq!(occurrences[0].date(), narrow_range.0);
+        assert_eq!(occurrences.last().unwrap().date(), narrow_range.1);
+    }
+
+    #[test]
+    fn no_rrule_returns_the_single_occurrence() {
+        let first_start = naive_datetime(2026, 6, 15);
+        let range = (
+            NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+        );
+
+        assert_eq!(expand_recurrence(None, first_start, range), vec![first_start]);
+    }
+}
@@ -1,31 +1,134 @@
-use chrono::{Datelike, Local, NaiveDate};
-use iced::widget::{button, column, container, row, scrollable, text};
+mod calendar_grid;
+mod events;
+
+use calendar_grid::CalendarGrid;
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use iced::widget::{button, column, container, pick_list, row, scrollable, text};
 use iced::{Application, Element, Length, Settings, Theme};
 
+use events::EventsCollection;
+
+/// Maximum number of event bars shown in a single day cell before the rest
+/// are collapsed into a "+k more" label. Shared with the `CalendarGrid`
+/// widget's own event-bar cap so Month view can't drift from Year/Week view.
+const MAX_EVENTS_PER_CELL: usize = calendar_grid::MAX_EVENT_BARS;
+
+/// How many days on either side of `current_date` are eagerly loaded into
+/// `events` at a time.
+const EVENTS_WINDOW_DAYS: i64 = 366;
+
+/// How close `current_date` has to get to the edge of the loaded events
+/// window before it's considered stale and reloaded.
+const EVENTS_RELOAD_MARGIN_DAYS: i64 = 31;
+
+/// Number of days `day` falls after `week_start` in a 7-day week, so grids
+/// can be laid out starting from an arbitrary first day of the week.
+fn days_after_week_start(week_start: Weekday, day: Weekday) -> i64 {
+    (day.num_days_from_monday() as i64 - week_start.num_days_from_monday() as i64).rem_euclid(7)
+}
+
+/// The ISO week number for a grid row whose first day is `date`. ISO weeks
+/// always run Monday through Sunday regardless of the app's configurable
+/// `week_start`, so a grid row that doesn't start on Monday (e.g. the default
+/// Sunday-first layout) has to look up the week number from that row's Monday
+/// (the first Monday on or after `date`), not from whichever day happens to
+/// lead the row.
+pub(crate) fn iso_week_number(date: NaiveDate) -> u32 {
+    let days_to_monday = (7 - date.weekday().num_days_from_monday() as i64) % 7;
+    let monday = date + chrono::Duration::days(days_to_monday);
+    monday.iso_week().week()
+}
+
+/// Shifts `date` by `delta` years, keeping the same month and day. `with_year`
+/// only fails for Feb 29 landing on a non-leap target year, so that case
+/// clamps to Feb 28 instead of leaving the date unchanged.
+fn shift_year(date: NaiveDate, delta: i32) -> NaiveDate {
+    let target_year = date.year() + delta;
+    date.with_year(target_year)
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(target_year, 2, 28).unwrap())
+}
+
 struct CalendarApp {
     current_date: NaiveDate,
     selected_date: Option<NaiveDate>,
+    view_mode: ViewMode,
+    events: EventsCollection,
+    ics_paths: Vec<String>,
+    events_range: (NaiveDate, NaiveDate),
+    week_start: Weekday,
+    show_iso_week: bool,
+}
+
+/// Flags passed in from `main`, carrying the `.ics` file paths given on the
+/// command line. Events are loaded lazily around `current_date` rather than
+/// eagerly here, since navigation can move the visible range well outside
+/// whatever window would be loaded at startup.
+#[derive(Debug, Default, Clone)]
+struct Flags {
+    ics_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewMode {
+    Month,
+    Year,
+    Week,
+}
+
+impl ViewMode {
+    const ALL: [ViewMode; 3] = [ViewMode::Month, ViewMode::Year, ViewMode::Week];
+}
+
+impl std::fmt::Display for ViewMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ViewMode::Month => "Month",
+            ViewMode::Year => "Year",
+            ViewMode::Week => "Week",
+        };
+        write!(f, "{}", label)
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     PreviousMonth,
     NextMonth,
+    PrevWeek,
+    NextWeek,
+    PrevYear,
+    NextYear,
     DateSelected(NaiveDate),
     BackToCalendar,
+    ViewModeSelected(ViewMode),
+    ToggleWeekStart,
+    ToggleIsoWeek,
 }
 
 impl Application for CalendarApp {
     type Message = Message;
     type Theme = Theme;
     type Executor = iced::executor::Default;
-    type Flags = ();
+    type Flags = Flags;
+
+    fn new(flags: Flags) -> (Self, iced::Command<Message>) {
+        let current_date = Local::now().date_naive();
+        let events_range = (
+            current_date - chrono::Duration::days(EVENTS_WINDOW_DAYS),
+            current_date + chrono::Duration::days(EVENTS_WINDOW_DAYS),
+        );
+        let events = EventsCollection::load_from_files(&flags.ics_paths, events_range);
 
-    fn new(_flags: ()) -> (Self, iced::Command<Message>) {
         (
             CalendarApp {
-                current_date: Local::now().date_naive(),
+                current_date,
                 selected_date: None,
+                view_mode: ViewMode::Month,
+                events,
+                ics_paths: flags.ics_paths,
+                events_range,
+                week_start: Weekday::Sun,
+                show_iso_week: false,
             },
             iced::Command::none(),
         )
@@ -47,20 +150,50 @@ impl Application for CalendarApp {
                     self.current_date.with_day(1).unwrap() + chrono::Duration::days(32);
                 self.current_date = self.current_date.with_day(1).unwrap();
             }
+            Message::PrevWeek => {
+                self.current_date = self.current_date - chrono::Duration::days(7);
+            }
+            Message::NextWeek => {
+                self.current_date = self.current_date + chrono::Duration::days(7);
+            }
+            Message::PrevYear => {
+                self.current_date = shift_year(self.current_date, -1);
+            }
+            Message::NextYear => {
+                self.current_date = shift_year(self.current_date, 1);
+            }
             Message::DateSelected(date) => {
                 self.selected_date = Some(date);
             }
             Message::BackToCalendar => {
                 self.selected_date = None;
             }
+            Message::ViewModeSelected(mode) => {
+                self.view_mode = mode;
+            }
+            Message::ToggleWeekStart => {
+                self.week_start = if self.week_start == Weekday::Sun {
+                    Weekday::Mon
+                } else {
+                    Weekday::Sun
+                };
+            }
+            Message::ToggleIsoWeek => {
+                self.show_iso_week = !self.show_iso_week;
+            }
         }
+        self.ensure_events_loaded();
         iced::Command::none()
     }
 
     fn view(&self) -> Element<Message> {
         match self.selected_date {
             Some(date) => self.detail_view(date),
-            None => self.calendar_view(),
+            None => match self.view_mode {
+                ViewMode::Month => self.calendar_view(),
+                ViewMode::Year => self.year_view(),
+                ViewMode::Week => self.week_view(),
+            },
         }
     }
 
@@ -70,11 +203,181 @@ impl Application for CalendarApp {
 }
 
 impl CalendarApp {
+    /// Reloads `events` around `current_date` once navigation has carried it
+    /// within `EVENTS_RELOAD_MARGIN_DAYS` of the edge of the currently loaded
+    /// window, so recurring events don't silently disappear after a few
+    /// `PrevYear`/`NextYear` jumps.
+    fn ensure_events_loaded(&mut self) {
+        let (loaded_start, loaded_end) = self.events_range;
+        let margin = chrono::Duration::days(EVENTS_RELOAD_MARGIN_DAYS);
+
+        let stale =
+            self.current_date < loaded_start + margin || self.current_date > loaded_end - margin;
+        if !stale {
+            return;
+        }
+
+        self.events_range = (
+            self.current_date - chrono::Duration::days(EVENTS_WINDOW_DAYS),
+            self.current_date + chrono::Duration::days(EVENTS_WINDOW_DAYS),
+        );
+        self.events = EventsCollection::load_from_files(&self.ics_paths, self.events_range);
+    }
+
+    fn view_mode_picker(&self) -> Element<Message> {
+        pick_list(&ViewMode::ALL[..], Some(self.view_mode), Message::ViewModeSelected).into()
+    }
+
+    /// Header controls for the week-start and ISO-week-column settings, shared
+    /// across the month/year/week views.
+    fn settings_controls(&self) -> Element<Message> {
+        let week_start_label = if self.week_start == Weekday::Mon {
+            "Week starts: Mon"
+        } else {
+            "Week starts: Sun"
+        };
+        let iso_week_label = if self.show_iso_week {
+            "ISO week: on"
+        } else {
+            "ISO week: off"
+        };
+
+        row![
+            button(week_start_label).on_press(Message::ToggleWeekStart),
+            button(iso_week_label).on_press(Message::ToggleIsoWeek),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    /// Builds the weekday-name header row. Each label is given the same
+    /// fixed width as the `CalendarGrid` columns below it (and the "Wk"
+    /// label the same width as its ISO-week column) so the two rows line up
+    /// in Month view.
+    fn weekday_header(&self) -> Element<Message> {
+        let mut header = row![];
+
+        if self.show_iso_week {
+            header = header.push(
+                text("Wk")
+                    .width(Length::Fixed(calendar_grid::ISO_WEEK_COLUMN_WIDTH))
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            );
+        }
+
+        let mut day = self.week_start;
+        for _ in 0..7 {
+            header = header.push(
+                text(day.to_string())
+                    .width(Length::Fixed(calendar_grid::CELL_WIDTH))
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            );
+            day = day.succ();
+        }
+
+        header.into()
+    }
+
+    /// Builds the 7-column grid of day buttons for the month containing `month_anchor`,
+    /// dimming days that fall outside that month. `cell_size` scales the button text to fit
+    /// the year-view thumbnails; the full Month view uses `CalendarGrid` instead.
+    /// `show_events` enables the small event-bar list under the day number; it's switched
+    /// off for the cramped year-view thumbnails.
+    fn month_days_grid(
+        &self,
+        month_anchor: NaiveDate,
+        cell_size: u16,
+        show_events: bool,
+    ) -> Element<Message> {
+        let mut grid = column![].spacing(2);
+
+        let first_day = month_anchor.with_day(1).unwrap();
+        let last_day = (month_anchor + chrono::Duration::days(32))
+            .with_day(1)
+            .unwrap()
+            - chrono::Duration::days(1);
+
+        let mut day = first_day
+            - chrono::Duration::days(days_after_week_start(self.week_start, first_day.weekday()));
+
+        while day <= last_day {
+            let mut week = row![].spacing(2);
+            if self.show_iso_week {
+                week = week.push(text(iso_week_number(day).to_string()).size(cell_size));
+            }
+            for _ in 0..7 {
+                let in_month = day.month() == month_anchor.month();
+                week = week.push(self.day_cell(day, in_month, cell_size, show_events));
+                day = day + chrono::Duration::days(1);
+            }
+            grid = grid.push(week);
+        }
+
+        grid.into()
+    }
+
+    /// Builds a single day cell: the day number, plus (when `show_events` is set) up to
+    /// [`MAX_EVENTS_PER_CELL`] small event-title bars with any remainder collapsed into a
+    /// "+k more" label.
+    fn day_cell(&self, day: NaiveDate, in_month: bool, cell_size: u16, show_events: bool) -> Element<Message> {
+        let button_style = if in_month {
+            iced::theme::Button::Primary
+        } else {
+            iced::theme::Button::Secondary
+        };
+
+        let mut cell_content = column![text(day.day().to_string()).size(cell_size)].spacing(2);
+
+        if show_events {
+            let day_events = self.events.for_date(&day);
+            for event in day_events.iter().take(MAX_EVENTS_PER_CELL) {
+                cell_content = cell_content.push(text(event.title.clone()).size(10));
+            }
+            if day_events.len() > MAX_EVENTS_PER_CELL {
+                cell_content = cell_content.push(
+                    text(format!("+{} more", day_events.len() - MAX_EVENTS_PER_CELL)).size(10),
+                );
+            }
+        }
+
+        button(cell_content)
+            .style(button_style)
+            .on_press(Message::DateSelected(day))
+            .into()
+    }
+
+    /// Builds the single-pass `CalendarGrid` widget for the month containing
+    /// `current_date`, replacing the old nested button/text grid.
+    fn month_calendar_grid(&self) -> Element<Message> {
+        let first_day = self.current_date.with_day(1).unwrap();
+        let last_day = (self.current_date + chrono::Duration::days(32))
+            .with_day(1)
+            .unwrap()
+            - chrono::Duration::days(1);
+
+        let first_cell = first_day
+            - chrono::Duration::days(days_after_week_start(self.week_start, first_day.weekday()));
+        let total_days = (last_day - first_cell).num_days() + 1;
+        let week_count = (total_days as usize).div_ceil(7);
+
+        CalendarGrid::new(
+            first_cell,
+            week_count,
+            self.current_date.month(),
+            Local::now().date_naive(),
+            self.selected_date,
+            &self.events,
+            self.show_iso_week,
+        )
+        .into()
+    }
+
     fn calendar_view(&self) -> Element<Message> {
         let mut content = column![].spacing(20);
 
-        // Month and year header
+        // Mode picker, and month/year header
         let header = row![
+            self.view_mode_picker(),
             button("<").on_press(Message::PreviousMonth),
             text(format!(
                 "{} {}",
@@ -84,53 +387,97 @@ impl CalendarApp {
             .width(Length::Fill)
             .horizontal_alignment(iced::alignment::Horizontal::Center),
             button(">").on_press(Message::NextMonth),
-        ];
+        ]
+        .spacing(10);
 
         content = content.push(header);
+        content = content.push(self.settings_controls());
+        content = content.push(self.weekday_header());
+        content = content.push(self.month_calendar_grid());
 
-        // Days of the week
-        let days = row![
-            text("Sun"),
-            text("Mon"),
-            text("Tue"),
-            text("Wed"),
-            text("Thu"),
-            text("Fri"),
-            text("Sat")
-        ];
-
-        content = content.push(days);
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
 
-        // Calendar grid
-        let first_day = self.current_date.with_day(1).unwrap();
-        let last_day = (self.current_date + chrono::Duration::days(32))
-            .with_day(1)
-            .unwrap()
-            - chrono::Duration::days(1);
+    /// Renders a 4x3 grid of mini-month thumbnails for the year containing `current_date`.
+    fn year_view(&self) -> Element<Message> {
+        let header = row![
+            self.view_mode_picker(),
+            button("<").on_press(Message::PrevYear),
+            text(format!("{}", self.current_date.year()))
+                .width(Length::Fill)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+            button(">").on_press(Message::NextYear),
+        ]
+        .spacing(10);
 
-        let mut day =
-            first_day - chrono::Duration::days(first_day.weekday().num_days_from_sunday() as i64);
+        let mut content = column![header, self.settings_controls()].spacing(20);
 
-        while day <= last_day {
-            let mut week = row![];
-            for _ in 0..7 {
-                let button_style = if day.month() == self.current_date.month() {
-                    iced::theme::Button::Primary
-                } else {
-                    iced::theme::Button::Secondary
-                };
+        for quarter in 0..3 {
+            let mut months_row = row![].spacing(20);
+            for column_index in 0..4 {
+                let month_number = quarter * 4 + column_index + 1;
+                let month_anchor = NaiveDate::from_ymd_opt(self.current_date.year(), month_number, 1)
+                    .unwrap();
 
-                week = week.push(
-                    button(text(day.day().to_string()))
-                        .style(button_style)
-                        .on_press(Message::DateSelected(day)),
-                );
+                let month_column = column![
+                    text(month_anchor.format("%B").to_string()).size(14),
+                    self.month_days_grid(month_anchor, 10, false),
+                ]
+                .spacing(4);
 
-                day = day + chrono::Duration::days(1);
+                months_row = months_row.push(month_column);
             }
-            content = content.push(week);
+            content = content.push(months_row);
         }
 
+        container(scrollable(content))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    /// Renders a single seven-column row for the week containing `current_date`.
+    fn week_view(&self) -> Element<Message> {
+        let week_start_date = self.current_date
+            - chrono::Duration::days(days_after_week_start(
+                self.week_start,
+                self.current_date.weekday(),
+            ));
+
+        let header = row![
+            self.view_mode_picker(),
+            button("<").on_press(Message::PrevWeek),
+            text(format!(
+                "Week of {}",
+                week_start_date.format("%B %d, %Y")
+            ))
+            .width(Length::Fill)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+            button(">").on_press(Message::NextWeek),
+        ]
+        .spacing(10);
+
+        let mut content = column![header, self.settings_controls()].spacing(20);
+        content = content.push(self.weekday_header());
+
+        let mut week = row![].spacing(2);
+        if self.show_iso_week {
+            week = week.push(text(iso_week_number(week_start_date).to_string()).size(20));
+        }
+        for offset in 0..7 {
+            let day = week_start_date + chrono::Duration::days(offset);
+            let in_month = day.month() == self.current_date.month();
+            week = week.push(self.day_cell(day, in_month, 20, true));
+        }
+        content = content.push(week);
+
         container(content)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -140,15 +487,36 @@ impl CalendarApp {
     }
 
     fn detail_view(&self, date: NaiveDate) -> Element<Message> {
-        let content = column![
+        let mut content = column![
             button("Back to Calendar").on_press(Message::BackToCalendar),
             text(format!("Date: {}", date.format("%B %d, %Y"))).size(24),
             text(format!("Day of the year: {}", date.ordinal())),
             text(format!("Week number: {}", date.iso_week().week())),
-            text(format!("Zodiac sign: {}", self.get_zodiac_sign(date)))
+            text(format!("Zodiac sign: {}", self.get_zodiac_sign(date))),
+            text(format!("Moon phase: {}", self.get_moon_phase(date))),
+            text("Events:"),
         ]
         .spacing(20);
 
+        let day_events = self.events.for_date(&date);
+        if day_events.is_empty() {
+            content = content.push(text("No events"));
+        } else {
+            for event in day_events {
+                let label = if event.all_day {
+                    format!("All day: {}", event.title)
+                } else {
+                    format!(
+                        "{}–{}: {}",
+                        event.start.format("%H:%M"),
+                        event.end.format("%H:%M"),
+                        event.title
+                    )
+                };
+                content = content.push(text(label));
+            }
+        }
+
         container(scrollable(content))
             .width(Length::Fill)
             .height(Length::Fill)
@@ -177,8 +545,75 @@ impl CalendarApp {
             _ => "Unknown",
         }
     }
+
+    /// Computes the moon phase for `date` from the number of days since the
+    /// known new moon of 2000-01-06, modulo the synodic month
+    /// (29.530588853 days), bucketed into the eight standard phase names.
+    fn get_moon_phase(&self, date: NaiveDate) -> &'static str {
+        const SYNODIC_MONTH: f64 = 29.530588853;
+        let known_new_moon = NaiveDate::from_ymd_opt(2000, 1, 6).unwrap();
+
+        let days_since = (date - known_new_moon).num_days() as f64;
+        let phase = (days_since.rem_euclid(SYNODIC_MONTH)) / SYNODIC_MONTH;
+
+        const PHASE_NAMES: [&str; 8] = [
+            "New Moon",
+            "Waxing Crescent",
+            "First Quarter",
+            "Waxing Gibbous",
+            "Full Moon",
+            "Waning Gibbous",
+            "Last Quarter",
+            "Waning Crescent",
+        ];
+
+        let index = (phase * 8.0).round() as usize % 8;
+        PHASE_NAMES[index]
+    }
 }
 
 fn main() -> iced::Result {
-    CalendarApp::run(Settings::default())
+    let ics_paths: Vec<String> = std::env::args().skip(1).collect();
+
+    CalendarApp::run(Settings::with_flags(Flags { ics_paths }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_app() -> CalendarApp {
+        let current_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        CalendarApp {
+            current_date,
+            selected_date: None,
+            view_mode: ViewMode::Month,
+            events: EventsCollection::new(),
+            ics_paths: Vec::new(),
+            events_range: (current_date, current_date),
+            week_start: Weekday::Sun,
+            show_iso_week: false,
+        }
+    }
+
+    #[test]
+    fn known_new_moon_is_new_moon() {
+        let app = test_app();
+        let new_moon = NaiveDate::from_ymd_opt(2000, 1, 6).unwrap();
+        assert_eq!(app.get_moon_phase(new_moon), "New Moon");
+    }
+
+    #[test]
+    fn half_synodic_month_later_is_full_moon() {
+        let app = test_app();
+        let full_moon = NaiveDate::from_ymd_opt(2000, 1, 21).unwrap();
+        assert_eq!(app.get_moon_phase(full_moon), "Full Moon");
+    }
+
+    #[test]
+    fn moon_phase_wraps_across_synodic_months() {
+        let app = test_app();
+        let much_later_new_moon = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
+        assert_eq!(app.get_moon_phase(much_later_new_moon), "New Moon");
+    }
 }